@@ -0,0 +1,226 @@
+// Copyright 2020 Parity Technologies (UK) Ltd.
+// This file is part of Polkadot.
+
+// Polkadot is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Polkadot is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Polkadot.  If not, see <http://www.gnu.org/licenses/>.
+
+//! A synthetic-workload benchmark harness for the votes db.
+//!
+//! Generates large, deterministic vote datasets against an in-memory
+//! `KeyValueDB` and measures the three hot paths of this subsystem as the store
+//! grows toward the `SESSION_COUNT_BEFORE_DROP` retention window:
+//!
+//! * `store_votes` insertion throughput (the dual key-index writes),
+//! * `prune_votes_older_than_session` latency (the prefix-scan erase), and
+//! * the cost of a full per-candidate tally scan (`tally_from_prefix`), the
+//!   scan that the incremental record read by `check_for_supermajority` avoids.
+//!
+//! The figures let `MAX_ITEMS_PER_DB_TRANSACTION` and the `DatabaseConfig`
+//! column/cache settings be tuned against measurement rather than guesswork.
+
+use super::*;
+
+use std::time::{Duration, Instant};
+
+use sp_keyring::Sr25519Keyring;
+
+/// Parameters describing the shape of a synthetic workload.
+#[derive(Debug, Clone, Copy)]
+pub struct BenchConfig {
+	/// Number of validators participating each session.
+	pub validator_count: usize,
+	/// Number of distinct candidates voted on per session.
+	pub candidates_per_session: usize,
+	/// Number of sessions to fill before measuring; capped at the retention
+	/// window as that is the largest the db is ever allowed to grow.
+	pub session_span: SessionIndex,
+	/// Every `collision_stride`-th validator additionally casts a contradicting
+	/// dispute vote, exercising the misbehavior path with deterministic
+	/// double-vote collisions. Set to `0` to disable.
+	pub collision_stride: usize,
+}
+
+impl Default for BenchConfig {
+	fn default() -> Self {
+		Self {
+			validator_count: 1000,
+			candidates_per_session: 50,
+			session_span: SESSION_COUNT_BEFORE_DROP,
+			collision_stride: 16,
+		}
+	}
+}
+
+/// The measured figures of a single benchmark run.
+#[derive(Debug, Clone)]
+pub struct BenchReport {
+	/// Total number of votes handed to `store_votes`.
+	pub votes_inserted: u64,
+	/// Wall-clock time spent inserting the whole dataset.
+	pub insert_duration: Duration,
+	/// Wall-clock time pruning roughly half of the retained sessions.
+	pub prune_duration: Duration,
+	/// Wall-clock time scanning the per-candidate prefix to tally one candidate.
+	pub supermajority_scan_duration: Duration,
+}
+
+impl BenchReport {
+	/// Insertions per second across the whole dataset.
+	pub fn inserts_per_second(&self) -> f64 {
+		let secs = self.insert_duration.as_secs_f64();
+		if secs > 0.0 {
+			self.votes_inserted as f64 / secs
+		} else {
+			f64::INFINITY
+		}
+	}
+}
+
+/// Deterministically derive a candidate hash from a session and candidate slot.
+fn synthetic_candidate_hash(session: SessionIndex, candidate: usize) -> CandidateHash {
+	let mut bytes = [0u8; 32];
+	bytes[0..4].copy_from_slice(&session.to_le_bytes());
+	bytes[4..12].copy_from_slice(&(candidate as u64).to_le_bytes());
+	CandidateHash(Hash::from(bytes))
+}
+
+/// Build a signed full statement for the given validator in a deterministic,
+/// repeatable way using the well-known test keyring.
+fn synthetic_statement(signing_context: &SigningContext, validator_index: ValidatorIndex, candidate_hash: CandidateHash, valid: bool) -> SignedFullStatement {
+	let keyring = Sr25519Keyring::iter()
+		.nth(validator_index as usize % Sr25519Keyring::iter().count())
+		.expect("keyring is non-empty; qed");
+	let statement = if valid {
+		Statement::Valid(candidate_hash)
+	} else {
+		Statement::Invalid(candidate_hash)
+	};
+	SignedFullStatement::benchmark_signed(
+		&keyring.pair().into(),
+		statement,
+		signing_context,
+		validator_index,
+	)
+}
+
+/// Produce a realistic mix of votes for a single candidate: mostly `Backing`
+/// and `ApprovalCheck`, plus a share of positive dispute votes. Exactly one
+/// vote per validator; the contradicting votes come from
+/// [`generate_candidate_collisions`].
+fn generate_candidate_votes(config: &BenchConfig, signing_context: &SigningContext, candidate: usize, candidate_hash: CandidateHash) -> Vec<Vote> {
+	let mut votes = Vec::with_capacity(config.validator_count);
+
+	for validator in 0..config.validator_count {
+		let validator_index = validator as ValidatorIndex;
+		// Rotate through the vote kinds so every flavour is exercised.
+		let vote = match (validator + candidate) % 4 {
+			0 | 1 => Vote::Backing {
+				attestation: ValidityAttestation::Implicit(Default::default()),
+				validator_index,
+				candidate_hash,
+			},
+			2 => Vote::ApprovalCheck {
+				sfs: synthetic_statement(signing_context, validator_index, candidate_hash, true),
+			},
+			_ => Vote::DisputePositive {
+				sfs: synthetic_statement(signing_context, validator_index, candidate_hash, true),
+			},
+		};
+		votes.push(vote);
+	}
+
+	votes
+}
+
+/// The deterministic double-vote collisions for a candidate: every
+/// `collision_stride`-th validator additionally casts a contradicting negative
+/// dispute vote. These are submitted in a *separate* `store_votes` call so the
+/// conflict is observed against committed state, exercising the misbehavior path.
+fn generate_candidate_collisions(config: &BenchConfig, signing_context: &SigningContext, candidate_hash: CandidateHash) -> Vec<Vote> {
+	if config.collision_stride == 0 {
+		return Vec::new()
+	}
+	(0..config.validator_count)
+		.step_by(config.collision_stride)
+		.map(|validator| Vote::DisputeNegative {
+			sfs: synthetic_statement(signing_context, validator as ValidatorIndex, candidate_hash, false),
+		})
+		.collect()
+}
+
+/// The relay parent a candidate is backed at. Each candidate gets a distinct
+/// relay parent, mirroring production where honestly backing many candidates
+/// across a session is not `MultipleBacking`.
+fn synthetic_relay_parent(session: SessionIndex, candidate: usize) -> Hash {
+	let mut bytes = [0u8; 32];
+	bytes[0..4].copy_from_slice(&session.to_le_bytes());
+	bytes[4..12].copy_from_slice(&(candidate as u64).to_le_bytes());
+	bytes[12] = 0xff;
+	Hash::from(bytes)
+}
+
+/// Run the benchmark end to end against a fresh in-memory db and return the
+/// measured figures.
+pub fn run(config: BenchConfig) -> BenchReport {
+	let db: Arc<dyn KeyValueDB> = Arc::new(kvdb_memorydb::create(columns::NUM_COLUMNS));
+	let span = config.session_span.min(SESSION_COUNT_BEFORE_DROP).max(1);
+
+	let mut votes_inserted = 0u64;
+	let insert_start = Instant::now();
+	for session in 0..span {
+		for candidate in 0..config.candidates_per_session {
+			let candidate_hash = synthetic_candidate_hash(session, candidate);
+			let signing_context = SigningContext {
+				session_index: session,
+				parent_hash: synthetic_relay_parent(session, candidate),
+			};
+			let votes = generate_candidate_votes(&config, &signing_context, candidate, candidate_hash);
+			votes_inserted += votes.len() as u64;
+			store_votes(&db, signing_context.clone(), config.validator_count, votes.as_slice())
+				.expect("synthetic sessions are never older than the waterlevel; qed");
+
+			// Submit the collisions against the now-committed first votes so the
+			// misbehavior classification actually fires.
+			let collisions = generate_candidate_collisions(&config, &signing_context, candidate_hash);
+			if !collisions.is_empty() {
+				votes_inserted += collisions.len() as u64;
+				store_votes(&db, signing_context, config.validator_count, collisions.as_slice())
+					.expect("synthetic sessions are never older than the waterlevel; qed");
+			}
+		}
+	}
+	let insert_duration = insert_start.elapsed();
+
+	// Measure the tally scan against a populated candidate.
+	let probe_session = span.saturating_sub(1);
+	let probe_candidate = synthetic_candidate_hash(probe_session, 0);
+	let scan_start = Instant::now();
+	// The full prefix scan underlying a quorum decision; the authoritative cost
+	// that the incremental tally in `store_votes` is there to avoid.
+	let _ = tally_from_prefix(&db, probe_session, probe_candidate);
+	let supermajority_scan_duration = scan_start.elapsed();
+
+	// Prune roughly half of the retained sessions.
+	let prune_to = span / 2;
+	let prune_start = Instant::now();
+	prune_votes_older_than_session(&db, prune_to)
+		.expect("pruning an in-memory db never fails; qed");
+	let prune_duration = prune_start.elapsed();
+
+	BenchReport {
+		votes_inserted,
+		insert_duration,
+		prune_duration,
+		supermajority_scan_duration,
+	}
+}
@@ -0,0 +1,145 @@
+// Copyright 2020 Parity Technologies (UK) Ltd.
+// This file is part of Polkadot.
+
+// Polkadot is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Polkadot is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Polkadot.  If not, see <http://www.gnu.org/licenses/>.
+
+use super::*;
+
+use sp_keyring::Sr25519Keyring;
+
+fn candidate(seed: u8) -> CandidateHash {
+	CandidateHash(Hash::repeat_byte(seed))
+}
+
+fn signing_context(session: SessionIndex) -> SigningContext {
+	SigningContext { session_index: session, parent_hash: Hash::repeat_byte(session as u8) }
+}
+
+fn backing(validator: ValidatorIndex, candidate_hash: CandidateHash) -> Vote {
+	Vote::Backing {
+		attestation: ValidityAttestation::Implicit(Default::default()),
+		validator_index: validator,
+		candidate_hash,
+	}
+}
+
+fn statement(signing_context: &SigningContext, validator: ValidatorIndex, candidate_hash: CandidateHash, valid: bool) -> SignedFullStatement {
+	let statement = if valid {
+		Statement::Valid(candidate_hash)
+	} else {
+		Statement::Invalid(candidate_hash)
+	};
+	SignedFullStatement::benchmark_signed(&Sr25519Keyring::Alice.pair().into(), statement, signing_context, validator)
+}
+
+fn memory_db() -> Arc<dyn KeyValueDB> {
+	Arc::new(kvdb_memorydb::create(columns::NUM_COLUMNS))
+}
+
+#[test]
+fn threshold_is_n_minus_f() {
+	// f = (n - 1) / 3
+	assert_eq!(supermajority_threshold(1), 1);
+	assert_eq!(supermajority_threshold(4), 3);
+	assert_eq!(supermajority_threshold(10), 7);
+}
+
+#[test]
+fn empty_tally_never_reaches_quorum() {
+	let tally = CandidateTally::default();
+	assert!(tally.resolve(candidate(1), 0).quorum.is_none());
+	assert!(tally.resolve(candidate(1), 10).quorum.is_none());
+}
+
+#[test]
+fn tally_resolves_each_side_at_threshold() {
+	let valid = CandidateTally { positive: 7, negative: 0 };
+	assert_eq!(valid.resolve(candidate(1), 10).quorum, Some(CandidateQuorum::Valid));
+
+	let invalid = CandidateTally { positive: 0, negative: 7 };
+	assert_eq!(invalid.resolve(candidate(1), 10).quorum, Some(CandidateQuorum::Invalid));
+
+	let short = CandidateTally { positive: 6, negative: 0 };
+	assert!(short.resolve(candidate(1), 10).quorum.is_none());
+}
+
+#[test]
+fn multiple_backing_of_distinct_candidates_is_misbehavior() {
+	let ctx = signing_context(1);
+	let first = backing(0, candidate(1));
+	let second = backing(0, candidate(2));
+	assert!(matches!(
+		classify_misbehavior(&first, &second, ctx),
+		Some(MisbehaviorProof { misbehavior: Misbehavior::MultipleBacking { .. }, .. }),
+	));
+}
+
+#[test]
+fn backing_same_candidate_is_not_misbehavior() {
+	let ctx = signing_context(1);
+	let vote = backing(0, candidate(1));
+	assert!(classify_misbehavior(&vote, &vote, ctx).is_none());
+}
+
+#[test]
+fn opposing_dispute_votes_are_validity_double_vote() {
+	let ctx = signing_context(1);
+	let c = candidate(1);
+	let positive = Vote::DisputePositive { sfs: statement(&ctx, 0, c, true) };
+	let negative = Vote::DisputeNegative { sfs: statement(&ctx, 0, c, false) };
+	assert!(matches!(
+		classify_misbehavior(&positive, &negative, ctx),
+		Some(MisbehaviorProof { misbehavior: Misbehavior::ValidityDoubleVote { .. }, .. }),
+	));
+}
+
+#[test]
+fn approval_contradicting_a_dispute_is_flagged() {
+	let ctx = signing_context(1);
+	let c = candidate(1);
+	let approval = Vote::ApprovalCheck { sfs: statement(&ctx, 0, c, true) };
+	let dispute = Vote::DisputeNegative { sfs: statement(&ctx, 0, c, false) };
+	assert!(matches!(
+		classify_misbehavior(&approval, &dispute, ctx),
+		Some(MisbehaviorProof { misbehavior: Misbehavior::ApprovalContradiction { .. }, .. }),
+	));
+}
+
+#[test]
+fn participation_by_validator_collects_across_candidates() {
+	let db = memory_db();
+	let ctx = signing_context(1);
+	let votes = vec![
+		backing(0, candidate(1)),
+		backing(0, candidate(2)),
+		backing(1, candidate(1)),
+	];
+	store_votes(&db, ctx, 10, votes.as_slice()).unwrap();
+
+	let participation = query_participation_by_validator(&db, 1, 0);
+	assert_eq!(participation.len(), 2);
+	assert!(participation.iter().all(|p| p.session == 1 && p.positive));
+}
+
+#[test]
+fn participation_by_candidate_collects_voters() {
+	let db = memory_db();
+	let ctx = signing_context(1);
+	let c = candidate(1);
+	store_votes(&db, ctx, 10, &[backing(0, c), backing(1, c), backing(2, c)]).unwrap();
+
+	let participation = query_participation_by_candidate(&db, 1, c);
+	assert_eq!(participation.len(), 3);
+	assert!(participation.iter().all(|p| p.candidate_hash == c && p.positive));
+}
@@ -32,12 +32,19 @@
 //! vote/s_{session_index}/c_{candidate_hash}/v_{validator_index}
 //! ```
 //!
-//! If the path exists the validator voted for that particular candidate.
-//! Stores an `Option<()>` as a marker, should never have a `Some(())` value.
+//! A per-validator index laid out for "what did this validator vote on"
+//! lookups; stores the `Vote` so cross-session participation can be rebuilt.
 //! ```text
 //! vote/s_{session_index}/v_{validator_index}/c_{candidate_hash}
 //! ```
 //!
+//! Collects proven misbehavior per validator and session so the evidence
+//! survives for the full `SESSION_COUNT_BEFORE_DROP` window and can be handed
+//! to the slashing subsystem at any later point.
+//! ```text
+//! vote/misbehavior/s_{session_index}/v_{validator_index}
+//! ```
+//!
 //! Common prefixes based on the session allows for fast and pain free deletion.
 //!
 //!
@@ -142,7 +149,7 @@ const OLDEST_SESSION_SLOT_ENTRY: &[u8] = b"vote/prune/waterlevel";
 
 ///
 #[inline(always)]
-fn derive_key_per_hash(prefix: &str, session: SessionIndex, validator: ValidatorIndex, candidate_hash: CandidateHash) -> String {
+fn derive_key_per_hash(session: SessionIndex, validator: ValidatorIndex, candidate_hash: CandidateHash) -> String {
 	format!(
 		"vote/s_{session_index}/c_{candidate_hash}/v_{validator_index}",
 		session_index = session,
@@ -153,7 +160,7 @@ fn derive_key_per_hash(prefix: &str, session: SessionIndex, validator: Validator
 
 /// A prefix with keys per validator.
 #[inline(always)]
-fn derive_key_per_val(prefix: &str, session: SessionIndex, validator: ValidatorIndex, candidate_hash: CandidateHash) -> String {
+fn derive_key_per_val(session: SessionIndex, validator: ValidatorIndex, candidate_hash: CandidateHash) -> String {
 	format!(
 		"vote/s_{session_index}/v_{validator_index}/c_{candidate_hash}",
 		session_index = session,
@@ -162,12 +169,92 @@ fn derive_key_per_val(prefix: &str, session: SessionIndex, validator: ValidatorI
 	)
 }
 
+/// The prefix covering every candidate a validator voted on in a session,
+/// laid out for per-validator lookup.
+#[inline(always)]
+fn derive_per_val_prefix(session: SessionIndex, validator: ValidatorIndex) -> String {
+	format!(
+		"vote/s_{session_index}/v_{validator_index}/",
+		session_index = session,
+		validator_index = validator,
+	)
+}
+
 /// Derive the prefix key for pruning.
 #[inline(always)]
 fn derive_prune_prefix(prefix: &str, session: SessionIndex) -> String {
 	format!("vote/s_{session_index}", session_index)
 }
 
+/// The per-candidate vote prefix, restricted to the individual validator
+/// entries so the incremental tally and reported markers are not scanned.
+#[inline(always)]
+fn derive_candidate_vote_prefix(session: SessionIndex, candidate_hash: CandidateHash) -> String {
+	format!(
+		"vote/s_{session_index}/c_{candidate_hash}/v_",
+		session_index = session,
+		candidate_hash = candidate_hash,
+	)
+}
+
+/// The key holding the incremental positive/negative tally for a candidate.
+#[inline(always)]
+fn derive_tally_key(session: SessionIndex, candidate_hash: CandidateHash) -> String {
+	format!(
+		"vote/s_{session_index}/c_{candidate_hash}/tally",
+		session_index = session,
+		candidate_hash = candidate_hash,
+	)
+}
+
+/// The marker key recording that a supermajority event was already emitted for
+/// a candidate, so repeated votes do not re-fire it.
+#[inline(always)]
+fn derive_quorum_reported_key(session: SessionIndex, candidate_hash: CandidateHash) -> String {
+	format!(
+		"vote/s_{session_index}/c_{candidate_hash}/quorum_reported",
+		session_index = session,
+		candidate_hash = candidate_hash,
+	)
+}
+
+/// A relay-parent-scoped index of a validator's backing votes. Backing two
+/// distinct candidates is only misbehavior when they share a relay parent, so
+/// the detection prefix is narrowed to that; kept under the session prefix so
+/// it is pruned along with everything else.
+#[inline(always)]
+fn derive_backing_key(session: SessionIndex, relay_parent: Hash, validator: ValidatorIndex, candidate_hash: CandidateHash) -> String {
+	format!(
+		"vote/s_{session_index}/backing/r_{relay_parent}/v_{validator_index}/c_{candidate_hash}",
+		session_index = session,
+		relay_parent = relay_parent,
+		validator_index = validator,
+		candidate_hash = candidate_hash,
+	)
+}
+
+/// The prefix covering every candidate a validator backed under one relay parent.
+#[inline(always)]
+fn derive_backing_prefix(session: SessionIndex, relay_parent: Hash, validator: ValidatorIndex) -> String {
+	format!(
+		"vote/s_{session_index}/backing/r_{relay_parent}/v_{validator_index}/",
+		session_index = session,
+		relay_parent = relay_parent,
+		validator_index = validator,
+	)
+}
+
+/// Derive the key under which proven misbehavior for a validator is collected
+/// for a given session.
+#[inline(always)]
+fn derive_misbehavior_key(session: SessionIndex, validator: ValidatorIndex) -> String {
+	format!(
+		"vote/misbehavior/s_{session_index}/v_{validator_index}",
+		session_index = session,
+		validator_index = validator,
+	)
+}
+
 
 /// Returns the oldest session index for which entries are not pruned yet.
 fn oldest_session_waterlevel(db: &Arc<dyn KeyValueDB>) -> SessionIndex {
@@ -293,7 +380,93 @@ impl Vote {
 	}
 }
 
-#[derive(Debug, Clone, Copy)]
+/// The category of a proven misbehavior.
+///
+/// Every variant bundles *both* offending `Vote`s verbatim; since a `Vote`
+/// already carries either the `SignedFullStatement` or the `ValidityAttestation`
+/// that was signed, the embedded votes together with the `SigningContext` of the
+/// enclosing [`MisbehaviorProof`] are sufficient to re-verify the signatures and
+/// feed the proof to slashing.
+#[derive(Debug, Clone, Encode, Decode, Eq, PartialEq)]
+enum Misbehavior {
+	/// The same validator signed both a positive and a negative statement for
+	/// one `candidate_hash`.
+	ValidityDoubleVote {
+		candidate_hash: CandidateHash,
+		valid: Vote,
+		invalid: Vote,
+	},
+	/// The validator backed two distinct candidates where the protocol permits
+	/// backing only one.
+	MultipleBacking {
+		first: Vote,
+		second: Vote,
+	},
+	/// An approval-check vote and a dispute vote of opposite validity from the
+	/// same validator on the same candidate, in either order.
+	ApprovalContradiction {
+		candidate_hash: CandidateHash,
+		approval: Vote,
+		dispute: Vote,
+	},
+}
+
+/// A self-contained, re-verifiable proof of validator misbehavior.
+#[derive(Debug, Clone, Encode, Decode, Eq, PartialEq)]
+struct MisbehaviorProof {
+	/// The kind of misbehavior together with the two offending votes.
+	misbehavior: Misbehavior,
+	/// The context both signatures were created in, required to re-verify them.
+	signing_context: SigningContext,
+}
+
+/// Classify a conflicting pair of votes cast by the same validator.
+///
+/// Returns `None` if the two votes do not actually constitute misbehavior (e.g.
+/// two identical votes, or two positive approval checks on distinct candidates,
+/// which is perfectly legal).
+fn classify_misbehavior(previous: &Vote, current: &Vote, signing_context: SigningContext) -> Option<MisbehaviorProof> {
+	let misbehavior = if previous.candidate_hash() == current.candidate_hash() {
+		let candidate_hash = current.candidate_hash();
+		match (previous, current) {
+			// A dispute or approval vote that disagrees with an earlier vote of
+			// opposite validity on the same candidate.
+			_ if previous.positive() != current.positive() => {
+				let is_approval = |v: &Vote| matches!(v, Vote::ApprovalCheck { .. });
+				let is_dispute = |v: &Vote| matches!(v, Vote::DisputePositive { .. } | Vote::DisputeNegative { .. });
+				if (is_approval(current) && is_dispute(previous)) || (is_approval(previous) && is_dispute(current)) {
+					let (approval, dispute) = if is_approval(current) {
+						(current.clone(), previous.clone())
+					} else {
+						(previous.clone(), current.clone())
+					};
+					Misbehavior::ApprovalContradiction { candidate_hash, approval, dispute }
+				} else {
+					let (valid, invalid) = if current.positive() {
+						(current.clone(), previous.clone())
+					} else {
+						(previous.clone(), current.clone())
+					};
+					Misbehavior::ValidityDoubleVote { candidate_hash, valid, invalid }
+				}
+			}
+			// Same candidate, same validity, nothing to prove.
+			_ => return None,
+		}
+	} else if matches!(previous, Vote::Backing { .. }) && matches!(current, Vote::Backing { .. }) {
+		// Backing two distinct candidates under the same relay parent; the caller
+		// is responsible for only pairing backings that share a relay parent.
+		Misbehavior::MultipleBacking { first: previous.clone(), second: current.clone() }
+	} else {
+		// Votes on distinct candidates that are not both backing votes are
+		// legitimate (e.g. approving several candidates).
+		return None
+	};
+
+	Some(MisbehaviorProof { misbehavior, signing_context })
+}
+
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
 enum CandidateQuorum {
 	/// The backed candidate is deemed valid.
 	Valid,
@@ -301,6 +474,60 @@ enum CandidateQuorum {
 	Invalid,
 }
 
+/// An incremental positive/negative vote count kept per candidate so quorum
+/// checks read a single record instead of rescanning the candidate prefix.
+#[derive(Debug, Clone, Copy, Default, Encode, Decode, Eq, PartialEq)]
+struct CandidateTally {
+	positive: u32,
+	negative: u32,
+}
+
+impl CandidateTally {
+	/// Resolve the tally against the `n - f` supermajority threshold.
+	fn resolve(&self, candidate_hash: CandidateHash, validator_count: usize) -> CandidateQuorumResult {
+		let threshold = supermajority_threshold(validator_count);
+		// An empty validator set, or a side with no votes at all, can never form
+		// a quorum even though the threshold degenerates to zero.
+		let quorum = if validator_count == 0 {
+			None
+		} else if self.positive > 0 && self.positive >= threshold {
+			Some(CandidateQuorum::Valid)
+		} else if self.negative > 0 && self.negative >= threshold {
+			Some(CandidateQuorum::Invalid)
+		} else {
+			None
+		};
+		CandidateQuorumResult {
+			candidate_hash,
+			positive: self.positive,
+			negative: self.negative,
+			threshold,
+			quorum,
+		}
+	}
+}
+
+/// The outcome of a quorum check alongside the `(positive, negative, threshold)`
+/// triple it was derived from, exposed so the disputes coordinator can decide
+/// when to conclude.
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+struct CandidateQuorumResult {
+	candidate_hash: CandidateHash,
+	positive: u32,
+	negative: u32,
+	threshold: u32,
+	/// `Some` once either side crossed the threshold.
+	quorum: Option<CandidateQuorum>,
+}
+
+/// The classic Byzantine-fault supermajority `n - f`, with `f = (n - 1) / 3`.
+#[inline(always)]
+fn supermajority_threshold(validator_count: usize) -> u32 {
+	let n = validator_count as u32;
+	let f = n.saturating_sub(1) / 3;
+	n - f
+}
+
 /// Output of the vote store action.
 #[derive(Debug, Clone)]
 enum VoteEvent {
@@ -313,7 +540,13 @@ enum VoteEvent {
 	/// A validator tried to vote twice
 	DoubleVote {
 		candidate: CandidateHash,
-		validator: ValidatorId,
+		validator: ValidatorIndex,
+	},
+	/// A conflicting pair of votes by the same validator was proven to be
+	/// misbehavior and a verifiable proof was stored for slashing.
+	Misbehavior {
+		validator: ValidatorIndex,
+		proof: MisbehaviorProof,
 	},
 	/// Either side of the votes has reached a super majority
 	SupermajorityReached{
@@ -325,49 +558,155 @@ enum VoteEvent {
 	},
 }
 
-fn check_for_supermajority(db: &Arc<dyn KeyValueDB>, session: SessionIndex, validator_count: usize) -> Result<Option<CandidateQuorum>> {
-	debug_assert!(session >= oldest_session_waterlevel());
-	
+/// Resolve whether either side of the vote for `candidate_hash` has reached the
+/// supermajority, reading the incremental per-candidate tally.
+fn check_for_supermajority(db: &Arc<dyn KeyValueDB>, session: SessionIndex, candidate_hash: CandidateHash, validator_count: usize) -> Result<CandidateQuorumResult> {
+	debug_assert!(session >= oldest_session_waterlevel(db));
+	let tally = read_db::<CandidateTally>(db, columns::DATA, derive_tally_key(session, candidate_hash).as_bytes())
+		.unwrap_or_default();
+	Ok(tally.resolve(candidate_hash, validator_count))
+}
+
+/// Recompute a candidate's tally from scratch by scanning the per-candidate
+/// vote prefix. The incremental counter maintained by [`store_votes`] counts
+/// the same entries; [`check_for_supermajority`] reads that counter and does
+/// not call this scan.
+fn tally_from_prefix(db: &Arc<dyn KeyValueDB>, session: SessionIndex, candidate_hash: CandidateHash) -> CandidateTally {
+	let mut tally = CandidateTally::default();
+	for (_key, raw) in db.iter_with_prefix(columns::DATA, derive_candidate_vote_prefix(session, candidate_hash).as_bytes()) {
+		let vote = Vote::decode(&mut &raw[..])
+			.expect("Database entries are all created from this module and thus must decode. qed");
+		if vote.positive() {
+			tally.positive += 1;
+		} else {
+			tally.negative += 1;
+		}
+	}
+	tally
 }
 
-fn store_votes(db: &Arc<dyn KeyValueDB>, session: SessionIndex, votes: &[Vote]) -> Result<Vec<VoteEvent>>> {
-	if session < get_pivot() {
-		log::warn!("Dropping request to store ancient votes.");
+fn store_votes(db: &Arc<dyn KeyValueDB>, signing_context: SigningContext, validator_count: usize, votes: &[Vote]) -> Result<Vec<VoteEvent>> {
+	let session = signing_context.session_index;
+	if session < oldest_session_waterlevel(db) {
+		log::warn!(target: TARGET, "Dropping request to store ancient votes.");
 		return Err(Error::ObsoleteVote)
 	}
-	let mut transaction = DBTransaction::with_capacity(votes.len());
-	let events: Vec<VoteEvent> = votes.into_iter()
-		.map(|vote| {
-			let k = derive_key(session, vote.validator());
-
-			if let Some(previous_vote) = read_db(db, columns::DATA, k) {
-				let previous_vote: Vote = previous_vote.decode()
-					.expect("Database entries are all created from this module and thus must decode. qed");
-
-				if previous_vote != vote {
-					unimplemented!("Derive a set of vote events
-					")
-					VoteEvent::DoubleVote {
-						validator: vote.validator(),
-						votes: vec![previous_vote, vote],
-					}
-					
-					// TODO clarify if a double vote means two opposing votes (pro and con)
-					// TODO or also two different vote kinds where both are positive
-				} else {
-					// if the votes are equivalent, just avoid the transaction element
-					VoteEvent::Success
-				}
+
+	let relay_parent = signing_context.parent_hash;
+	let mut transaction = db.transaction();
+	let mut events = Vec::with_capacity(votes.len());
+	// Accumulate tallies across the batch so several votes for one candidate in
+	// a single call observe each other's increments before the db write lands.
+	let mut tallies: HashMap<CandidateHash, CandidateTally> = HashMap::new();
+	// Proven misbehavior, collected per validator so several offenses in one
+	// batch accumulate rather than overwrite each other in the db.
+	let mut misbehaviors: HashMap<ValidatorIndex, Vec<MisbehaviorProof>> = HashMap::new();
+	// Backing votes seen for this relay parent, per validator, seeded lazily from
+	// the db so within-batch and cross-batch `MultipleBacking` are both caught.
+	let mut backings: HashMap<ValidatorIndex, Vec<Vote>> = HashMap::new();
+	// Candidates whose supermajority event already fired, so repeated votes for
+	// one candidate in a single batch do not re-emit it.
+	let mut reported: HashSet<CandidateHash> = HashSet::new();
+	// The first vote kept per (validator, candidate) this batch, seeded lazily
+	// from committed db state so a conflicting pair that arrives in one batch is
+	// detected just like one split across batches.
+	let mut seen: HashMap<(ValidatorIndex, CandidateHash), Vote> = HashMap::new();
+
+	for vote in votes.iter() {
+		let validator = vote.validator();
+		let candidate_hash = vote.candidate_hash();
+		let per_hash = derive_key_per_hash(session, validator, candidate_hash);
+
+		// Has this validator already cast a vote for this very candidate, whether
+		// committed or earlier in this same batch?
+		let previous = match seen.get(&(validator, candidate_hash)) {
+			Some(previous_vote) => Some(previous_vote.clone()),
+			None => read_db::<Vote>(db, columns::DATA, per_hash.as_bytes()),
+		};
+		if let Some(previous_vote) = previous {
+			if &previous_vote == vote {
+				// An exact duplicate carries no new information.
+				continue
+			}
+
+			// The conflicting pair is either provable misbehavior or, failing
+			// classification, a plain double vote.
+			if let Some(proof) = classify_misbehavior(&previous_vote, vote, signing_context.clone()) {
+				misbehaviors.entry(validator).or_default().push(proof.clone());
+				events.push(VoteEvent::Misbehavior { validator, proof });
 			} else {
-				let v = vote.encode();
-				transaction.put(columns::DATA, k ,v);
-				if supermajority_reached {
-					VoteEvent::SupermajorityReached
-				} else {
-					VoteEvent::Stored
+				events.push(VoteEvent::DoubleVote { candidate: candidate_hash, validator });
+			}
+			continue
+		}
+
+		// The first vote this (validator, candidate); remember it so later votes
+		// in the batch are compared against it rather than against the db alone.
+		seen.insert((validator, candidate_hash), vote.clone());
+
+		// Backing a second, distinct candidate under the same relay parent is
+		// `MultipleBacking`. Only backings sharing this relay parent are compared,
+		// so honestly backing candidates across the session is not flagged.
+		if matches!(vote, Vote::Backing { .. }) {
+			let prior = backings.entry(validator).or_insert_with(|| {
+				db.iter_with_prefix(columns::DATA, derive_backing_prefix(session, relay_parent, validator).as_bytes())
+					.map(|(_key, raw)| Vote::decode(&mut &raw[..])
+						.expect("Database entries are all created from this module and thus must decode. qed"))
+					.collect()
+			});
+			for previous_vote in prior.iter() {
+				if let Some(proof) = classify_misbehavior(previous_vote, vote, signing_context.clone()) {
+					misbehaviors.entry(validator).or_default().push(proof.clone());
+					events.push(VoteEvent::Misbehavior { validator, proof });
 				}
 			}
-	}).collect();
+			prior.push(vote.clone());
+			transaction.put(columns::DATA, derive_backing_key(session, relay_parent, validator, candidate_hash).as_bytes(), vote.encode().as_slice());
+		}
+
+		// Store the vote under both the per-candidate and per-validator index.
+		let encoded = vote.encode();
+		transaction.put(columns::DATA, per_hash.as_bytes(), encoded.as_slice());
+		transaction.put(columns::DATA, derive_key_per_val(session, validator, candidate_hash).as_bytes(), encoded.as_slice());
+		events.push(VoteEvent::Stored);
+
+		// Keep the incremental tally in lock-step with the write and resolve it
+		// against the supermajority threshold.
+		let tally_key = derive_tally_key(session, candidate_hash);
+		let tally = tallies.entry(candidate_hash).or_insert_with(|| {
+			read_db::<CandidateTally>(db, columns::DATA, tally_key.as_bytes()).unwrap_or_default()
+		});
+		if vote.positive() {
+			tally.positive += 1;
+		} else {
+			tally.negative += 1;
+		}
+		transaction.put(columns::DATA, tally_key.as_bytes(), tally.encode().as_slice());
+
+		let result = tally.resolve(candidate_hash, validator_count);
+		if result.quorum.is_some() {
+			// Fire `SupermajorityReached` exactly once per candidate. The marker
+			// write is only visible after this transaction commits, so a batch
+			// with many votes for one candidate is also guarded in memory.
+			let reported_key = derive_quorum_reported_key(session, candidate_hash);
+			let already_reported = reported.contains(&candidate_hash)
+				|| read_db::<()>(db, columns::DATA, reported_key.as_bytes()).is_some();
+			if !already_reported {
+				reported.insert(candidate_hash);
+				transaction.put(columns::DATA, reported_key.as_bytes(), ().encode().as_slice());
+				events.push(VoteEvent::SupermajorityReached { quorum: result });
+			}
+		}
+	}
+
+	// Append this batch's proofs to whatever is already stored for each
+	// validator so evidence of every offense survives the retention window.
+	for (validator, proofs) in misbehaviors {
+		let key = derive_misbehavior_key(session, validator);
+		let mut stored = read_db::<Vec<MisbehaviorProof>>(db, columns::DATA, key.as_bytes()).unwrap_or_default();
+		stored.extend(proofs);
+		transaction.put(columns::DATA, key.as_bytes(), stored.encode().as_slice());
+	}
 
 	db.write_transaction(transaction)?;
 
@@ -383,15 +722,60 @@ pub async fn on_session_change(current_session: SessionIndex) -> Result<()> {
 
 
 
-pub async fn store_vote(current_session: SessionIndex, vote: Vote) -> Result<()> {
+/// A single vote a validator cast, as surfaced by the participation query; the
+/// one source of truth for "what did validator X vote on across recent sessions"
+/// for the disputes and slashing subsystems.
+#[derive(Debug, Clone, Encode, Decode, Eq, PartialEq)]
+struct DisputeParticipation {
+	/// The session the vote belongs to.
+	session: SessionIndex,
+	/// The candidate that was voted on.
+	candidate_hash: CandidateHash,
+	/// `true` if the vote supports the candidate's validity.
+	positive: bool,
+	/// The raw vote, carrying the original attestation for re-verification.
+	attestation: Vote,
+}
 
-	Ok(())
+impl DisputeParticipation {
+	fn from_vote(session: SessionIndex, vote: Vote) -> Self {
+		Self {
+			session,
+			candidate_hash: vote.candidate_hash(),
+			positive: vote.positive(),
+			attestation: vote,
+		}
+	}
 }
 
-pub async fn query(validator: ValidatorId) -> Result<()> {
-	// lookup all sessions this validator had duty
-	// 
-	Ok(())
+/// Collect every candidate `validator` voted on, walking all non-pruned
+/// sessions from the waterlevel up to and including `upto_session` via the
+/// `vote/s/v/c` per-validator index.
+fn query_participation_by_validator(db: &Arc<dyn KeyValueDB>, upto_session: SessionIndex, validator: ValidatorIndex) -> Vec<DisputeParticipation> {
+	let mut participation = Vec::new();
+	for session in oldest_session_waterlevel(db)..=upto_session {
+		for (_key, raw) in db.iter_with_prefix(columns::DATA, derive_per_val_prefix(session, validator).as_bytes()) {
+			let vote = Vote::decode(&mut &raw[..])
+				.expect("Database entries are all created from this module and thus must decode. qed");
+			participation.push(DisputeParticipation::from_vote(session, vote));
+		}
+	}
+	participation
+}
+
+/// Collect every vote cast on `candidate_hash`, walking all non-pruned sessions
+/// from the waterlevel up to and including `upto_session` via the `vote/s/c/v`
+/// per-candidate index.
+fn query_participation_by_candidate(db: &Arc<dyn KeyValueDB>, upto_session: SessionIndex, candidate_hash: CandidateHash) -> Vec<DisputeParticipation> {
+	let mut participation = Vec::new();
+	for session in oldest_session_waterlevel(db)..=upto_session {
+		for (_key, raw) in db.iter_with_prefix(columns::DATA, derive_candidate_vote_prefix(session, candidate_hash).as_bytes()) {
+			let vote = Vote::decode(&mut &raw[..])
+				.expect("Database entries are all created from this module and thus must decode. qed");
+			participation.push(DisputeParticipation::from_vote(session, vote));
+		}
+	}
+	participation
 }
 
 /// The bitfield distribution subsystem.
@@ -431,23 +815,51 @@ impl VotesDB {
 		Context: SubsystemContext<Message = VotesDBMessage>,
 	{
 		// work: process incoming messages from the overseer and process accordingly.
+		// The `VotesDBMessage` variants matched below (`StoreVotes`, `Query`,
+		// `QueryByCandidate`, `QueryQuorum`) are defined alongside the other
+		// subsystem messages in `polkadot-subsystem`; each carries the context
+		// and `oneshot` response channel the handlers here expect.
 		let mut state = ProtocolState::default();
 		loop {
 			let message = ctx.recv().await?;
 			match message {
 				FromOverseer::Communication {
-					msg: VotesDBMessage::Query (session, validator),
+					msg: VotesDBMessage::Query { session, validator, tx },
+				} => {
+					let participation = query_participation_by_validator(&self.inner, session, validator);
+					if tx.send(participation).is_err() {
+						log::warn!(target: TARGET, "Query response receiver for validator {:?} was dropped", validator)
+					}
+				}
+
+				FromOverseer::Communication {
+					msg: VotesDBMessage::QueryByCandidate { session, candidate_hash, tx },
+				} => {
+					let participation = query_participation_by_candidate(&self.inner, session, candidate_hash);
+					if tx.send(participation).is_err() {
+						log::warn!(target: TARGET, "Query response receiver for candidate {:?} was dropped", candidate_hash)
+					}
+				}
+
+				FromOverseer::Communication {
+					msg: VotesDBMessage::QueryQuorum { session, candidate_hash, validator_count, tx },
 				} => {
-					if let Err() = query(validator).await {
-						log::warn!(target: TARGET, "Failed to query disputes validator {} pariticpated", validator)
+					match check_for_supermajority(&self.inner, session, candidate_hash, validator_count) {
+						Ok(quorum) => if tx.send(quorum).is_err() {
+							log::warn!(target: TARGET, "Quorum query response receiver for candidate {:?} was dropped", candidate_hash)
+						},
+						Err(e) => log::warn!(target: TARGET, "Failed to resolve quorum for candidate {:?}: {:?}", candidate_hash, e),
 					}
 				}
 
 				FromOverseer::Communication {
-					msg: VotesDBMessage::StoreVote { vote },
+					msg: VotesDBMessage::StoreVotes { signing_context, validator_count, votes },
 				} => {
-					if let Err() = store_vote(vote).await {
-						log::warn!(target: TARGET, "Failed to store disputes vote pariticpated")
+					match store_votes(&self.inner, signing_context, validator_count, votes.as_slice()) {
+						Ok(events) => for event in events {
+							trace!(target: TARGET, "Vote store event: {:?}", event);
+						},
+						Err(e) => log::warn!(target: TARGET, "Failed to store votes: {:?}", e),
 					}
 				}
 				FromOverseer::Signal(
@@ -475,5 +887,8 @@ impl VotesDB {
 	}
 }
 
+#[cfg(feature = "bench")]
+pub mod bench;
+
 #[cfg(test)]
 mod tests;
\ No newline at end of file